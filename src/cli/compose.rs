@@ -3,32 +3,41 @@ use std::{
     fs::read_to_string,
     sync::{Arc, RwLock},
 };
-use super::{rainconfig::RainConfigStruct, super::parser::raindocument::RainDocument, RainComposerCli};
+use super::{
+    rainconfig::{compute_cache_key, ProcessType, RainConfigStruct},
+    super::parser::raindocument::RainDocument,
+    RainComposerCli,
+};
+
+mod cache;
+mod watch;
+
+pub use watch::watch_target;
 
 /// Composes only the given .rain files based on provided options
 pub async fn compose_target(opts: RainComposerCli) -> anyhow::Result<String> {
+    compose(&opts).await
+}
+
+/// does the actual work of [`compose_target`], taking `opts` by reference so
+/// [`watch_target`] can re-run it on every filesystem change without having
+/// to take ownership of the options each time
+pub(crate) async fn compose(opts: &RainComposerCli) -> anyhow::Result<String> {
     let force = opts.force.unwrap_or(false);
     let local_data_only = opts.local_data_only.unwrap_or(false);
-    let store = if let Some(rainconfig_path) = &opts.config {
-        let rainconfig = RainConfigStruct::read(rainconfig_path)?;
-        if force {
-            rainconfig.force_build_store()?
-        } else {
-            rainconfig.build_store()?
-        }
-    } else {
-        Arc::new(RwLock::new(Store::default()))
+    let no_cache = opts.no_cache.unwrap_or(false);
+    if opts.clear_cache.unwrap_or(false) {
+        cache::clear()?;
+    }
+
+    let rainconfig = match &opts.config {
+        Some(rainconfig_path) => Some(RainConfigStruct::read(rainconfig_path)?),
+        None => None,
     };
 
     // read the dotrain text
     let text = read_to_string(&opts.input)?;
 
-    // instantiate the RainDocument
-    let mut rain_document = RainDocument::new(text, Some(store.clone()), 0, None);
-
-    // parse
-    rain_document.parse(!local_data_only).await;
-
     // generate rainlang
     let entrypoints = opts
         .entrypoints
@@ -36,5 +45,82 @@ pub async fn compose_target(opts: RainComposerCli) -> anyhow::Result<String> {
         .map(|e| e.as_str())
         .collect::<Vec<&str>>();
 
-    Ok(rain_document.compose(&entrypoints)?)
+    // resolve every dotrain/meta/deployer input once so the result can seed both
+    // the cache key and the store, instead of paying for the reads/fetches twice.
+    // `strict_uris` mirrors the distinction `build_store`/`force_build_store`
+    // make of their own `process` call.
+    let strict_uris = !force;
+    let processed = match &rainconfig {
+        Some(rainconfig) => Some(rainconfig.process(strict_uris).await?),
+        None => None,
+    };
+
+    let cache_key = if no_cache {
+        None
+    } else {
+        Some(build_cache_key(
+            &rainconfig,
+            processed.as_ref(),
+            opts,
+            &text,
+            &entrypoints,
+            local_data_only,
+        ))
+    };
+    if let Some(key) = &cache_key {
+        if let Some(cached) = cache::read(key) {
+            return Ok(cached);
+        }
+    }
+
+    let store = match (&rainconfig, processed) {
+        (Some(rainconfig), Some(processed)) => rainconfig.build_store_from(processed, strict_uris)?,
+        _ => Arc::new(RwLock::new(Store::default())),
+    };
+
+    // instantiate the RainDocument
+    let mut rain_document = RainDocument::new(text, Some(store.clone()), 0, None);
+
+    // parse
+    rain_document.parse(!local_data_only).await;
+
+    let composed = rain_document.compose(&entrypoints)?;
+
+    if let Some(key) = &cache_key {
+        cache::write(key, &composed)?;
+    }
+
+    Ok(composed)
+}
+
+/// derives the cache key covering every input that can affect the composed
+/// output: the dotrain/meta/deployer material already resolved into `processed`,
+/// the target dotrain's own text, the requested entrypoints, and any flag (like
+/// `local_data_only`) that changes what `compose` does with otherwise-identical input
+fn build_cache_key(
+    rainconfig: &Option<RainConfigStruct>,
+    processed: Option<&ProcessType>,
+    opts: &RainComposerCli,
+    text: &str,
+    entrypoints: &[&str],
+    local_data_only: bool,
+) -> [u8; 32] {
+    let (mut dotrains, metas, deployers) = match processed {
+        Some(ProcessType(dotrains, metas, deployers)) => (dotrains.clone(), &metas[..], &deployers[..]),
+        None => (vec![], &[][..], &[][..]),
+    };
+    let subgraphs = rainconfig
+        .as_ref()
+        .and_then(|c| c.subgraphs.clone())
+        .unwrap_or_default();
+    dotrains.push((opts.input.clone(), text.to_string()));
+
+    compute_cache_key(
+        &dotrains,
+        metas,
+        deployers,
+        &subgraphs,
+        entrypoints,
+        local_data_only,
+    )
 }
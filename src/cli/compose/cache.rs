@@ -0,0 +1,64 @@
+use std::{fs, path::PathBuf};
+use alloy_primitives::hex;
+
+/// root composed rainlang outputs are cached under, scoped per-user so a cache
+/// entry can't be planted or read by another local user sharing the machine:
+/// `$XDG_CACHE_HOME/rainlang-compose` if set, else `~/.cache/rainlang-compose`,
+/// falling back to a username-namespaced folder under the system temp dir only
+/// when neither can be determined
+fn cache_dir() -> PathBuf {
+    if let Some(xdg_cache) = std::env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        return PathBuf::from(xdg_cache).join("rainlang-compose");
+    }
+    if let Some(home) = std::env::var_os("HOME").filter(|v| !v.is_empty()) {
+        return PathBuf::from(home).join(".cache").join("rainlang-compose");
+    }
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    std::env::temp_dir().join(format!("rainlang-compose-cache-{user}"))
+}
+
+fn cache_file(key: &[u8; 32]) -> PathBuf {
+    cache_dir().join(hex::encode(key))
+}
+
+/// returns the previously cached composed output for `key`, if any. Refuses to
+/// follow a symlink planted at the cache path - matching the write side, which
+/// replaces rather than follows one - so another local user racing us in a
+/// shared `$TMPDIR` fallback can't substitute their own content as "cached" output
+pub(crate) fn read(key: &[u8; 32]) -> Option<String> {
+    let target = cache_file(key);
+    if fs::symlink_metadata(&target).ok()?.file_type().is_symlink() {
+        return None;
+    }
+    fs::read_to_string(target).ok()
+}
+
+/// caches `composed` under `key` for the next invocation to pick up. Writes to
+/// a sibling temp file and renames it into place rather than writing the cache
+/// file directly, so a pre-existing symlink planted at the target path is
+/// replaced instead of followed
+pub(crate) fn write(key: &[u8; 32], composed: &str) -> anyhow::Result<()> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    }
+    let target = cache_file(key);
+    let tmp = dir.join(format!("{}.{}.tmp", hex::encode(key), std::process::id()));
+    fs::write(&tmp, composed)?;
+    fs::rename(&tmp, &target)?;
+    Ok(())
+}
+
+/// removes every cached composed output
+pub(crate) fn clear() -> anyhow::Result<()> {
+    let dir = cache_dir();
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::{sync::mpsc::channel, time::Duration};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use super::super::{rainconfig::RainConfigStruct, RainComposerCli};
+
+/// filesystem events are coalesced for this long before a rebuild is triggered,
+/// so a save that touches several files only recomposes once
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches the rainconfig's `include` directories, its local `meta`/`deployer`
+/// artifact sources (plus the rainconfig file and the target dotrain itself)
+/// for changes and recomposes on every one of them, printing the new output or
+/// the diagnostics produced while doing so. This is the interactive counterpart
+/// to running [`super::compose_target`] once per process/edit.
+pub async fn watch_target(opts: RainComposerCli) -> anyhow::Result<()> {
+    let (tx, rx) = channel::<notify::Result<Event>>();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })?;
+
+    if let Some(rainconfig_path) = &opts.config {
+        watcher.watch(rainconfig_path, RecursiveMode::NonRecursive)?;
+        if let Ok(rainconfig) = RainConfigStruct::read(rainconfig_path) {
+            if let Some(included_dirs) = &rainconfig.include {
+                for dir in included_dirs {
+                    watcher.watch(dir, RecursiveMode::Recursive)?;
+                }
+            }
+            let (local_paths, remote_count) = rainconfig.meta_and_deployer_paths();
+            for path in &local_paths {
+                watcher.watch(path, RecursiveMode::NonRecursive)?;
+            }
+            if remote_count > 0 {
+                eprintln!(
+                    "note: {remote_count} meta/deployer source(s) are remote (http(s)://, ipfs://) and can't be watched for changes"
+                );
+            }
+        }
+    }
+    watcher.watch(&opts.input, RecursiveMode::NonRecursive)?;
+
+    recompose_and_report(&opts).await;
+
+    while rx.recv().is_ok() {
+        // drain whatever else arrived while we were debouncing so a burst of
+        // saves across many files only triggers a single rebuild
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        recompose_and_report(&opts).await;
+    }
+
+    Ok(())
+}
+
+/// recomposes the target with the current options and prints the result,
+/// or the diagnostics, to stdout/stderr
+async fn recompose_and_report(opts: &RainComposerCli) {
+    match super::compose(opts).await {
+        Ok(composed) => println!("{composed}"),
+        Err(diagnostics) => eprintln!("{diagnostics}"),
+    }
+}
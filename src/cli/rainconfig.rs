@@ -5,10 +5,18 @@ use rain_meta::{Store, DeployerResponse, RainMetaDocumentV1Item};
 use std::{
     path::PathBuf,
     sync::{Arc, RwLock},
-    fs::{read, read_to_string, read_dir},
+    fs::{read_to_string, read_dir},
     collections::HashMap,
 };
 
+mod archive;
+mod decompress;
+mod fetch;
+use fetch::fetch_bytes;
+
+pub(crate) const RAINCONFIG_EXTENDS_DESCRIPTION: &str = r"A path or list of paths of other rainconfig(s) to inherit from. Parent rainconfigs are read first (recursively), and this rainconfig's 'include', 'subgraphs', 'meta' and 'deployers' are merged on top of them, so later layers append to and override earlier ones. An inherited entry can be dropped with a '!' prefix (for 'include'/'subgraphs') or via the top level 'remove' field (for 'meta'/'deployers').";
+pub(crate) const RAINCONFIG_REMOVE_DESCRIPTION: &str = r"Removes entries that were inherited through 'extends' but are not directly expressible as a '!'-prefixed entry, keyed by the field they should be removed from ('meta' paths, 'deployers' hashes).";
+
 pub(crate) const RAINCONFIG_DESCRIPTION: &str = r"
 Description:
 rainconfig.json provides configuration details and information required for .rain compiler.
@@ -17,25 +25,41 @@ usually it should be placed at the root directory of the working workspace and n
 'rainconfig.json', however if this is not desired at times, it is possible to pass any path for 
 rainconfig when using the dotrain command using --config option.
 
-all fields in the rainconfig are optional and are as follows:
+all fields in the rainconfig are optional and are as follows (any string value may reference
+'${VAR}'/'$VAR' environment variables, optionally with a '${VAR:-default}' fallback, and the
+pseudo variable '${workspaceRoot}' which resolves to the directory containing the rainconfig
+file the reference appears in):
+
+  - extends: A path or list of paths of other rainconfig(s) to inherit from. Parent
+  rainconfigs are read first (recursively, cycles are rejected), and this rainconfig's
+  'include', 'subgraphs', 'meta' and 'deployers' are merged on top of them. An inherited
+  'include'/'subgraphs' entry can be dropped with a '!' prefix, anything else inherited
+  can be dropped through the 'remove' field.
 
-  - include: Specifies a list of directories (files/folders) to be included and watched. 
-  'src' files are included by default and folders will be watched recursively for .rain files. 
+  - include: Specifies a list of directories (files/folders) to be included and watched.
+  'src' files are included by default and folders will be watched recursively for .rain files.
   These files will be available as dotrain meta in the cas so if their hash is specified in a
-  compilation target they will get resolved.
+  compilation target they will get resolved. An entry may also point at a '.tar'/'.tar.gz'/'.tgz'
+  archive, whose contained .rain files are walked exactly as a directory's would be.
 
   - subgraphs: Additional subgraph endpoint URLs to include when searching for metas of 
   specified meta hashes in a rainlang document.
 
-  - meta: List of paths of local meta files as binary or utf8 encoded text file containing hex 
-  string starting with 0x.
+  - meta: List of paths of local meta files as binary or utf8 encoded text file containing hex
+  string starting with 0x. A path may also be a 'file://', 'https://' or 'ipfs://' URI, in which
+  case it is fetched from disk/network/IPFS gateway instead of read relative to the CWD. Gzip and
+  zstd compressed meta files (detected by magic bytes) are transparently decompressed before
+  hashing; a brotli compressed meta file is too, provided its path ends in '.br'.
 
   - deployers: List of ExpressionDeployers data sets which represents all the data required for 
   reproducing it on a local evm, paired with their corresponding hash as a key/value pair, each 
-  pair has the fields that hold a path to disk location to read data from, 'expressionDeployer', 
-  'parser', 'store', 'interpreter' fields should point to contract json artifact where their 
-  bytecode and deployed bytecode can be read from and 'constructionMeta' is specified the same 
-  as any other meta.
+  pair has the fields that hold a path (or 'file://'/'https://'/'ipfs://' URI) to the location to
+  read data from, 'expressionDeployer', 'parser', 'store', 'interpreter' fields should point to
+  contract json artifact where their bytecode and deployed bytecode can be read from and
+  'constructionMeta' is specified the same as any other meta.
+
+  - remove: Removes 'meta'/'deployers' entries inherited through 'extends', keyed by the field
+  they should be removed from ('meta' paths, 'deployers' hashes).
 ";
 pub(crate) const RAINCONFIG_INCLUDE_DESCRIPTION: &str = r"Specifies a list of directories (files/folders) to be included and watched. 'src' files are included by default and folders will be watched recursively for .rain files. These files will be available as dotrain meta in the cas so if their hash is specified in a compilation target they will get resolved.";
 pub(crate) const RAINCONFIG_SUBGRAPHS_DESCRIPTION: &str = r"Additional subgraph endpoint URLs to include when searching for metas of specified meta hashes in a rainlang document.";
@@ -52,6 +76,16 @@ pub enum RainConfigMetaType {
     Hex(PathBuf),
 }
 
+impl RainConfigMetaType {
+    /// the underlying path/source of this meta entry, regardless of its encoding
+    fn path(&self) -> &PathBuf {
+        match self {
+            RainConfigMetaType::Binary(path) => path,
+            RainConfigMetaType::Hex(path) => path,
+        }
+    }
+}
+
 /// Data structure of deserialized deployer item from rainconfig.json
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -63,36 +97,202 @@ pub struct RainConfigDeployer {
     pub interpreter: PathBuf,
 }
 
-/// Data structure of deserialized rainconfig.json
+/// A single path or a list of paths another rainconfig extends from
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RainConfigExtends {
+    Single(PathBuf),
+    Multiple(Vec<PathBuf>),
+}
+
+impl RainConfigExtends {
+    /// normalizes this into a list of paths, in the order they should be layered
+    fn into_paths(self) -> Vec<PathBuf> {
+        match self {
+            RainConfigExtends::Single(path) => vec![path],
+            RainConfigExtends::Multiple(paths) => paths,
+        }
+    }
+}
+
+/// Explicit removal directives for fields that cannot carry a '!'-prefixed
+/// entry of their own (ie keyed/structured fields), applied after all
+/// `extends` layers have been merged
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RainConfigRemove {
+    pub meta: Option<Vec<PathBuf>>,
+    pub deployers: Option<Vec<String>>,
+}
+
+/// Data structure of deserialized rainconfig.json
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct RainConfigStruct {
+    pub extends: Option<RainConfigExtends>,
     pub include: Option<Vec<PathBuf>>,
     pub subgraphs: Option<Vec<String>>,
     pub meta: Option<Vec<RainConfigMetaType>>,
     pub deployers: Option<HashMap<String, RainConfigDeployer>>,
+    pub remove: Option<RainConfigRemove>,
 }
 
-struct ProcessType(
-    Vec<(PathBuf, String)>,
-    Vec<(Vec<u8>, Vec<u8>)>,
-    Vec<DeployerResponse>,
+pub(crate) struct ProcessType(
+    pub(crate) Vec<(PathBuf, String)>,
+    pub(crate) Vec<(Vec<u8>, Vec<u8>)>,
+    pub(crate) Vec<DeployerResponse>,
 );
 
 struct ArtifactBytecode(Option<Vec<u8>>, Option<Vec<u8>>);
 
 impl RainConfigStruct {
-    /// reads rainconfig from the given path
+    /// reads rainconfig from the given path, resolving any `extends` chain and
+    /// applying merge/removal semantics across the resulting layers. Every
+    /// `!`/`remove` directive is already resolved by the time [`Self::read_layer`]
+    /// returns, so there's nothing left to apply here
     pub fn read(path: &PathBuf) -> anyhow::Result<RainConfigStruct> {
-        let content = read(path)?;
-        let rainconfig: RainConfigStruct = serde_json::from_slice(&content)?;
-        Ok(rainconfig)
+        let mut visited = vec![];
+        Self::read_layer(path, &mut visited)
+    }
+
+    /// reads a single rainconfig layer and recursively merges in everything it extends,
+    /// with `visited` tracking the canonicalized paths already read in this chain so a
+    /// cycle (A extends B extends A) is reported instead of recursing forever
+    fn read_layer(path: &PathBuf, visited: &mut Vec<PathBuf>) -> anyhow::Result<RainConfigStruct> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if visited.contains(&canonical) {
+            return Err(anyhow::anyhow!(format!(
+                "cyclic 'extends' chain detected at {:?}",
+                path
+            )));
+        }
+        visited.push(canonical);
+
+        let workspace_root = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let content = read_to_string(path)?;
+        let interpolated = interpolate(&content, &workspace_root)?;
+        let own: RainConfigStruct = serde_json::from_str(&interpolated)?;
+
+        let result = if let Some(extends) = own.extends.clone() {
+            let mut merged = RainConfigStruct::default();
+            for parent_path in extends.into_paths() {
+                let resolved_parent_path = if parent_path.is_absolute() {
+                    parent_path
+                } else {
+                    workspace_root.join(parent_path)
+                };
+                let parent = Self::read_layer(&resolved_parent_path, visited)?;
+                merged = merged.merge(parent);
+            }
+            merged.merge(own)
+        } else {
+            // nothing below this layer for a `!`/`remove` directive to cancel,
+            // so any it carries are no-ops: drop them rather than leaving stray
+            // `!`-prefixed markers in the resolved config
+            RainConfigStruct::default().merge(own)
+        };
+
+        visited.pop();
+        Ok(result)
     }
 
-    pub fn read_included_files(&self, force: bool) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    /// merges `other` (a higher, later layer) on top of `self` (a lower, earlier
+    /// layer). `other`'s `!`-prefixed `include`/`subgraphs` entries and its
+    /// `remove` directive are resolved immediately against `self` - ie only
+    /// against layers merged *before* `other` - before `other`'s own positive
+    /// entries are appended, so a layer can only cancel what it inherited and a
+    /// later layer's re-addition of the same entry always wins
+    fn merge(mut self, other: RainConfigStruct) -> RainConfigStruct {
+        let (other_include, removed_include) =
+            partition_path_removals(other.include.unwrap_or_default());
+        if !removed_include.is_empty() {
+            let removed_include: Vec<PathBuf> =
+                removed_include.iter().map(|p| normalize_path(p)).collect();
+            if let Some(include) = &mut self.include {
+                include.retain(|p| !removed_include.contains(&normalize_path(p)));
+            }
+        }
+        if !other_include.is_empty() {
+            self.include.get_or_insert_with(Vec::new).extend(other_include);
+        }
+
+        let (other_subgraphs, removed_subgraphs) =
+            partition_string_removals(other.subgraphs.unwrap_or_default());
+        if !removed_subgraphs.is_empty() {
+            if let Some(subgraphs) = &mut self.subgraphs {
+                subgraphs.retain(|s| !removed_subgraphs.contains(s));
+            }
+        }
+        if !other_subgraphs.is_empty() {
+            self.subgraphs
+                .get_or_insert_with(Vec::new)
+                .extend(other_subgraphs);
+        }
+
+        if let Some(other_remove) = &other.remove {
+            if let Some(meta_removals) = &other_remove.meta {
+                let meta_removals: Vec<PathBuf> =
+                    meta_removals.iter().map(|p| normalize_path(p)).collect();
+                if let Some(meta) = &mut self.meta {
+                    meta.retain(|m| !meta_removals.contains(&normalize_path(m.path())));
+                }
+            }
+            if let Some(deployer_removals) = &other_remove.deployers {
+                if let Some(deployers) = &mut self.deployers {
+                    for hash in deployer_removals {
+                        deployers.remove(hash);
+                    }
+                }
+            }
+        }
+        if let Some(other_meta) = other.meta {
+            self.meta.get_or_insert_with(Vec::new).extend(other_meta);
+        }
+        if let Some(other_deployers) = other.deployers {
+            self.deployers
+                .get_or_insert_with(HashMap::new)
+                .extend(other_deployers);
+        }
+
+        self.extends = None;
+        self.remove = None;
+        self
+    }
+
+    /// splits the paths of every configured meta and deployer artifact source
+    /// (construction meta plus the four bytecode artifacts per deployer) into
+    /// the ones on local disk (bare paths and `file://` URIs, the latter with
+    /// the scheme stripped) and the count of ones addressed by an
+    /// `http(s)://`/`ipfs://` URI, since only the former can be registered
+    /// with a filesystem watcher
+    pub(crate) fn meta_and_deployer_paths(&self) -> (Vec<PathBuf>, usize) {
+        let mut local = vec![];
+        let mut remote = 0;
+        let mut visit = |path: &PathBuf| match local_watch_path(path) {
+            Some(local_path) => local.push(local_path),
+            None => remote += 1,
+        };
+        if let Some(metas) = &self.meta {
+            for m in metas {
+                visit(m.path());
+            }
+        }
+        if let Some(deployers) = &self.deployers {
+            for deployer in deployers.values() {
+                visit(deployer.construction_meta.path());
+                visit(&deployer.expression_deployer);
+                visit(&deployer.parser);
+                visit(&deployer.store);
+                visit(&deployer.interpreter);
+            }
+        }
+        (local, remote)
+    }
+
+    pub async fn read_included_files(&self, force: bool) -> anyhow::Result<Vec<(PathBuf, String)>> {
         let mut files_contents = vec![];
         if let Some(included_dirs) = &self.include {
             for included_dir in included_dirs {
-                match read_dotrain_files(included_dir, force) {
+                match read_dotrain_files(included_dir, force).await {
                     Ok(v) => files_contents.extend(v),
                     Err(e) => {
                         if !force {
@@ -105,11 +305,11 @@ impl RainConfigStruct {
         Ok(files_contents)
     }
 
-    fn process(&self, force: bool) -> anyhow::Result<ProcessType> {
+    pub(crate) async fn process(&self, force: bool) -> anyhow::Result<ProcessType> {
         let mut dotrains = vec![];
         let mut metas = vec![];
         let mut npe2_deployers = vec![];
-        match self.read_included_files(force) {
+        match self.read_included_files(force).await {
             Ok(v) => dotrains.extend(v),
             Err(e) => {
                 if !force {
@@ -119,12 +319,12 @@ impl RainConfigStruct {
         }
         if let Some(all_metas) = &self.meta {
             for m in all_metas {
-                read_meta(m, &mut metas, force)?;
+                read_meta(m, &mut metas, force).await?;
             }
         }
         if let Some(deployers) = &self.deployers {
             for (hash, deployer) in deployers {
-                match read_deployer(hash, deployer) {
+                match read_deployer(hash, deployer).await {
                     Ok(v) => {
                         npe2_deployers.push(v);
                     }
@@ -140,14 +340,32 @@ impl RainConfigStruct {
     }
 
     /// Build a Store instance from all specified configuraion in rainconfig
-    pub fn build_store(&self) -> anyhow::Result<Arc<RwLock<Store>>> {
+    pub async fn build_store(&self) -> anyhow::Result<Arc<RwLock<Store>>> {
+        self.build_store_from(self.process(true).await?, true)
+    }
+
+    /// Builds a Store instance from all specified configuraion in rainconfig by ignoring all erroneous path/items
+    pub async fn force_build_store(&self) -> anyhow::Result<Arc<RwLock<Store>>> {
+        self.build_store_from(self.process(false).await?, false)
+    }
+
+    /// builds a Store from an already-resolved [`ProcessType`], so a caller that
+    /// needs the resolved inputs for another purpose (eg computing a cache key)
+    /// can process once and reuse the result here instead of re-reading/re-fetching.
+    /// `strict_uris` mirrors the distinction between [`Self::build_store`] and
+    /// [`Self::force_build_store`]: when set, a dotrain path that can't be turned
+    /// into a valid utf-8 URI fails the whole build instead of being skipped
+    pub(crate) fn build_store_from(
+        &self,
+        ProcessType(dotrains, metas, mut deployers): ProcessType,
+        strict_uris: bool,
+    ) -> anyhow::Result<Arc<RwLock<Store>>> {
         let temp: Vec<String> = vec![];
         let subgraphs = if let Some(sgs) = &self.subgraphs {
             sgs
         } else {
             &temp
         };
-        let ProcessType(dotrains, metas, mut deployers) = self.process(true)?;
         let mut store = Store::default();
         store.add_subgraphs(subgraphs);
         for (hash, bytes) in metas {
@@ -157,7 +375,7 @@ impl RainConfigStruct {
             if let Some(uri) = path.to_str() {
                 let uri = uri.to_string();
                 store.set_dotrain(&text, &uri, true)?;
-            } else {
+            } else if strict_uris {
                 return Err(anyhow::anyhow!(format!(
                     "could not derive a valid utf-8 encoded URI from path: {:?}",
                     path
@@ -169,41 +387,213 @@ impl RainConfigStruct {
         }
         Ok(Arc::new(RwLock::new(store)))
     }
+}
 
-    /// Builds a Store instance from all specified configuraion in rainconfig by ignoring all erroneous path/items
-    pub fn force_build_store(&self) -> anyhow::Result<Arc<RwLock<Store>>> {
-        let temp: Vec<String> = vec![];
-        let subgraphs = if let Some(sgs) = &self.subgraphs {
-            sgs
+/// bump this whenever a change to the compiler/composer pipeline would make a
+/// previously cached composed output stale, invalidating every existing entry
+pub(crate) const CACHE_VERSION: u8 = 1;
+
+/// fingerprints every resolved input that can affect a `compose` result - dotrain
+/// file contents, meta bytes, deployer artifact bytes, the subgraph URL list, the
+/// requested entrypoints and the `local_data_only` flag (which changes whether
+/// `RainDocument::parse` consults remote data for otherwise-identical input) - in
+/// a deterministic, path-sorted order, prefixed by [`CACHE_VERSION`], so the same
+/// inputs always produce the same cache key
+pub(crate) fn compute_cache_key(
+    dotrains: &[(PathBuf, String)],
+    metas: &[(Vec<u8>, Vec<u8>)],
+    deployers: &[DeployerResponse],
+    subgraphs: &[String],
+    entrypoints: &[&str],
+    local_data_only: bool,
+) -> [u8; 32] {
+    let mut dotrains = dotrains.to_vec();
+    dotrains.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut metas = metas.to_vec();
+    metas.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut deployers: Vec<&DeployerResponse> = deployers.iter().collect();
+    deployers.sort_by(|a, b| a.tx_hash.cmp(&b.tx_hash));
+    let mut subgraphs = subgraphs.to_vec();
+    subgraphs.sort();
+    let mut entrypoints = entrypoints.to_vec();
+    entrypoints.sort();
+
+    let mut input = vec![CACHE_VERSION];
+    for (path, text) in &dotrains {
+        input.extend_from_slice(path.to_string_lossy().as_bytes());
+        input.extend_from_slice(text.as_bytes());
+    }
+    for (hash, bytes) in &metas {
+        input.extend_from_slice(hash);
+        input.extend_from_slice(bytes);
+    }
+    for deployer in &deployers {
+        input.extend_from_slice(&deployer.tx_hash);
+        input.extend_from_slice(&deployer.bytecode);
+        input.extend_from_slice(&deployer.meta_bytes);
+        input.extend_from_slice(&deployer.parser);
+        input.extend_from_slice(&deployer.store);
+        input.extend_from_slice(&deployer.interpreter);
+    }
+    for subgraph in &subgraphs {
+        input.extend_from_slice(subgraph.as_bytes());
+    }
+    for entrypoint in &entrypoints {
+        input.extend_from_slice(entrypoint.as_bytes());
+    }
+    input.push(local_data_only as u8);
+
+    keccak256(input).0
+}
+
+/// expands `${VAR}`/`$VAR`/`${workspaceRoot}` references in the raw rainconfig
+/// text against the process environment before it is parsed as JSON, so paths
+/// and subgraph URLs can carry secrets and stay relative to the config file
+fn interpolate(content: &str, workspace_root: &std::path::Path) -> anyhow::Result<String> {
+    let bytes = content.as_bytes();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'$' {
+            let ch = content[i..].chars().next().unwrap();
+            out.push(ch);
+            i += ch.len_utf8();
+            continue;
+        }
+        if bytes.get(i + 1) == Some(&b'{') {
+            let end = content[i + 2..]
+                .find('}')
+                .map(|p| i + 2 + p)
+                .ok_or_else(|| anyhow::anyhow!("unterminated '${{' in rainconfig"))?;
+            out.push_str(&json_escape(&resolve_interpolation(
+                &content[i + 2..end],
+                workspace_root,
+            )?));
+            i = end + 1;
         } else {
-            &temp
-        };
-        let ProcessType(dotrains, metas, mut deployers) = self.process(false)?;
-        let mut store = Store::default();
-        store.add_subgraphs(subgraphs);
-        for (hash, bytes) in metas {
-            store.update_with(&hex::decode(&hash)?, &bytes);
+            let start = i + 1;
+            let mut end = start;
+            while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+                end += 1;
+            }
+            if end == start {
+                out.push('$');
+                i += 1;
+            } else {
+                out.push_str(&json_escape(&resolve_interpolation(
+                    &content[start..end],
+                    workspace_root,
+                )?));
+                i = end;
+            }
         }
-        for (path, text) in dotrains {
-            if let Some(uri) = path.to_str() {
-                let uri = uri.to_string();
-                store.set_dotrain(&text, &uri, true)?;
+    }
+    Ok(out)
+}
+
+/// resolves a single `VAR` or `VAR:-default` expression found inside a
+/// `${...}`/`$...` interpolation, with `workspaceRoot` handled as a pseudo
+/// variable pointing at the directory containing the rainconfig file
+fn resolve_interpolation(expr: &str, workspace_root: &std::path::Path) -> anyhow::Result<String> {
+    if expr == "workspaceRoot" {
+        return Ok(workspace_root.to_string_lossy().to_string());
+    }
+    let (name, default) = match expr.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (expr, None),
+    };
+    match std::env::var(name) {
+        Ok(value) => Ok(value),
+        Err(_) => default.map(str::to_string).ok_or_else(|| {
+            anyhow::anyhow!(format!(
+                "environment variable '{name}' is not set and no default was provided"
+            ))
+        }),
+    }
+}
+
+/// escapes `value` for splicing into the JSON string literal it's interpolated
+/// into, so a substituted secret or path containing `"`, `\` or a control
+/// character can't corrupt the surrounding JSON or terminate the string early
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).expect("string always serializes");
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// lexically normalizes `path` (collapsing `.` components and resolving `..`
+/// against the preceding component) without touching the filesystem, so an
+/// inherited `"./src"` and a child's `"src"` - or `"a/../src"` and `"src"` -
+/// compare equal even though they were spelled differently
+fn normalize_path(path: &std::path::Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !matches!(result.components().next_back(), Some(std::path::Component::ParentDir) | None) {
+                    result.pop();
+                } else {
+                    result.push("..");
+                }
             }
+            other => result.push(other.as_os_str()),
         }
-        while let Some(deployer) = deployers.pop() {
-            store.set_deployer_from_query_response(deployer);
+    }
+    result
+}
+
+/// splits a list of paths into the ones to keep and the ones a `!`-prefixed
+/// entry asks to remove (with the `!` stripped off)
+fn partition_path_removals(items: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut keep = vec![];
+    let mut removed = vec![];
+    for item in items {
+        match item.to_string_lossy().strip_prefix('!') {
+            Some(stripped) => removed.push(PathBuf::from(stripped)),
+            None => keep.push(item),
         }
-        Ok(Arc::new(RwLock::new(store)))
     }
+    (keep, removed)
+}
+
+/// splits a list of strings into the ones to keep and the ones a `!`-prefixed
+/// entry asks to remove (with the `!` stripped off)
+fn partition_string_removals(items: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut keep = vec![];
+    let mut removed = vec![];
+    for item in items {
+        match item.strip_prefix('!') {
+            Some(stripped) => removed.push(stripped.to_string()),
+            None => keep.push(item),
+        }
+    }
+    (keep, removed)
+}
+
+/// the on-disk path to watch for `path`, if it resolves to one: a bare path or
+/// a `file://` URI resolves locally (the latter with its scheme stripped),
+/// while `http(s)://`/`ipfs://` sources have nothing on this machine to watch
+fn local_watch_path(path: &PathBuf) -> Option<PathBuf> {
+    let addr = path.to_string_lossy();
+    if let Some(local_path) = addr.strip_prefix("file://") {
+        return Some(PathBuf::from(local_path));
+    }
+    if addr.starts_with("http://") || addr.starts_with("https://") || addr.starts_with("ipfs://") {
+        return None;
+    }
+    Some(path.clone())
 }
 
 /// reads rain files recursively from the provided path
-fn read_dotrain_files(path: &PathBuf, force: bool) -> anyhow::Result<Vec<(PathBuf, String)>> {
+async fn read_dotrain_files(path: &PathBuf, force: bool) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    if archive::is_dotrain_archive(path) {
+        return archive::read_dotrain_archive(path).await;
+    }
     let mut files_contents = vec![];
     for read_dir_result in read_dir(path)? {
         let dir = read_dir_result?.path();
         if dir.is_dir() {
-            match read_dotrain_files(&dir, force) {
+            match Box::pin(read_dotrain_files(&dir, force)).await {
                 Ok(v) => files_contents.extend(v),
                 Err(e) => {
                     if !force {
@@ -212,7 +602,16 @@ fn read_dotrain_files(path: &PathBuf, force: bool) -> anyhow::Result<Vec<(PathBu
                 }
             }
         } else if dir.is_file() {
-            if let Some(ext) = dir.extension() {
+            if archive::is_dotrain_archive(&dir) {
+                match archive::read_dotrain_archive(&dir).await {
+                    Ok(v) => files_contents.extend(v),
+                    Err(e) => {
+                        if !force {
+                            Err(e)?
+                        }
+                    }
+                }
+            } else if let Some(ext) = dir.extension() {
                 if ext == "rain" {
                     match read_to_string(&dir) {
                         Ok(v) => files_contents.push((dir.clone(), v)),
@@ -229,14 +628,15 @@ fn read_dotrain_files(path: &PathBuf, force: bool) -> anyhow::Result<Vec<(PathBu
     Ok(files_contents)
 }
 
-fn read_meta(
+async fn read_meta(
     meta: &RainConfigMetaType,
     metas: &mut Vec<(Vec<u8>, Vec<u8>)>,
     force: bool,
 ) -> anyhow::Result<()> {
     match meta {
-        RainConfigMetaType::Binary(binary_meta_path) => match read(binary_meta_path) {
+        RainConfigMetaType::Binary(binary_meta_path) => match fetch_bytes(binary_meta_path).await {
             Ok(data) => {
+                let data = decompress::decompress(data, binary_meta_path);
                 metas.push((keccak256(&data).0.to_vec(), data));
             }
             Err(e) => {
@@ -245,11 +645,19 @@ fn read_meta(
                 }
             }
         },
-        RainConfigMetaType::Hex(hex_meta_path) => match read_to_string(hex_meta_path) {
-            Ok(hex_string) => match hex::decode(hex_string) {
-                Ok(data) => {
-                    metas.push((keccak256(&data).0.to_vec(), data));
-                }
+        RainConfigMetaType::Hex(hex_meta_path) => match fetch_bytes(hex_meta_path).await {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(hex_string) => match hex::decode(hex_string.trim()) {
+                    Ok(data) => {
+                        let data = decompress::decompress(data, hex_meta_path);
+                        metas.push((keccak256(&data).0.to_vec(), data));
+                    }
+                    Err(e) => {
+                        if !force {
+                            return Err(anyhow::anyhow!(format!("{:?} at {:?}", e, hex_meta_path)));
+                        }
+                    }
+                },
                 Err(e) => {
                     if !force {
                         return Err(anyhow::anyhow!(format!("{:?} at {:?}", e, hex_meta_path)));
@@ -266,15 +674,18 @@ fn read_meta(
     Ok(())
 }
 
-fn read_deployer(hash: &str, deployer: &RainConfigDeployer) -> anyhow::Result<DeployerResponse> {
+async fn read_deployer(
+    hash: &str,
+    deployer: &RainConfigDeployer,
+) -> anyhow::Result<DeployerResponse> {
     let mut metas = vec![];
-    read_meta(&deployer.construction_meta, &mut metas, false)?;
+    read_meta(&deployer.construction_meta, &mut metas, false).await?;
     let (meta_hash, meta_bytes) = if metas.len() == 1 {
         metas.pop().unwrap()
     } else {
         return Err(anyhow::anyhow!("could not reaed construction meta!"));
     };
-    let exp_deployer = read_bytecode(&deployer.expression_deployer)?;
+    let exp_deployer = read_bytecode(&deployer.expression_deployer).await?;
     let bytecode = if let Some(v) = exp_deployer.0 {
         v
     } else {
@@ -301,19 +712,22 @@ fn read_deployer(hash: &str, deployer: &RainConfigDeployer) -> anyhow::Result<De
         meta_hash,
         meta_bytes,
         bytecode,
-        parser: read_bytecode(&deployer.parser)?
+        parser: read_bytecode(&deployer.parser)
+            .await?
             .0
             .ok_or(anyhow::anyhow!(format!(
                 "could not read parser deployed bytecode at {:?}",
                 deployer.parser
             )))?,
-        store: read_bytecode(&deployer.store)?
+        store: read_bytecode(&deployer.store)
+            .await?
             .0
             .ok_or(anyhow::anyhow!(format!(
                 "could not read store deployed bytecode at {:?}",
                 deployer.store
             )))?,
-        interpreter: read_bytecode(&deployer.interpreter)?
+        interpreter: read_bytecode(&deployer.interpreter)
+            .await?
             .0
             .ok_or(anyhow::anyhow!(format!(
                 "could not read interpreter deployed bytecode at {:?}",
@@ -324,8 +738,204 @@ fn read_deployer(hash: &str, deployer: &RainConfigDeployer) -> anyhow::Result<De
     })
 }
 
-fn read_bytecode(path: &PathBuf) -> anyhow::Result<ArtifactBytecode> {
-    let content = read(path)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn paths(items: &[&str]) -> Vec<PathBuf> {
+        items.iter().map(PathBuf::from).collect()
+    }
+
+    fn with_include(include: &[&str]) -> RainConfigStruct {
+        RainConfigStruct {
+            include: Some(paths(include)),
+            ..RainConfigStruct::default()
+        }
+    }
+
+    #[test]
+    fn merge_appends_includes_with_later_layer_last() {
+        let grandparent = with_include(&["common"]);
+        let parent = with_include(&["other"]);
+        let merged = grandparent.merge(parent);
+        assert_eq!(merged.include, Some(paths(&["common", "other"])));
+    }
+
+    #[test]
+    fn merge_removal_only_cancels_earlier_layers() {
+        let grandparent = with_include(&["common"]);
+        let middle = with_include(&["!common"]);
+        let leaf = with_include(&["common"]);
+
+        let merged = grandparent.merge(middle).merge(leaf);
+
+        assert_eq!(merged.include, Some(paths(&["common"])));
+    }
+
+    #[test]
+    fn merge_removal_strips_inherited_entry_when_not_reintroduced() {
+        let grandparent = with_include(&["common", "keep"]);
+        let middle = with_include(&["!common"]);
+
+        let merged = grandparent.merge(middle);
+
+        assert_eq!(merged.include, Some(paths(&["keep"])));
+    }
+
+    #[test]
+    fn merge_removal_matches_across_differently_spelled_equivalent_paths() {
+        let grandparent = with_include(&["./src"]);
+        let middle = with_include(&["!a/../src"]);
+
+        let merged = grandparent.merge(middle);
+
+        assert_eq!(merged.include, Some(vec![]));
+    }
+
+    #[test]
+    fn merge_remove_directive_deletes_inherited_meta_and_deployers() {
+        let mut grandparent = RainConfigStruct::default();
+        grandparent.meta = Some(vec![RainConfigMetaType::Binary(PathBuf::from("a.meta"))]);
+        grandparent.deployers = Some(HashMap::from([(
+            "0x01".to_string(),
+            RainConfigDeployer {
+                construction_meta: RainConfigMetaType::Binary(PathBuf::from("a.meta")),
+                expression_deployer: PathBuf::from("a"),
+                parser: PathBuf::from("a"),
+                store: PathBuf::from("a"),
+                interpreter: PathBuf::from("a"),
+            },
+        )]));
+
+        let mut child = RainConfigStruct::default();
+        child.remove = Some(RainConfigRemove {
+            meta: Some(vec![PathBuf::from("a.meta")]),
+            deployers: Some(vec!["0x01".to_string()]),
+        });
+
+        let merged = grandparent.merge(child);
+
+        assert_eq!(merged.meta, Some(vec![]));
+        assert_eq!(merged.deployers, Some(HashMap::new()));
+    }
+
+    #[test]
+    fn merge_clears_extends_and_remove() {
+        let mut other = RainConfigStruct::default();
+        other.extends = Some(RainConfigExtends::Single(PathBuf::from("x")));
+        other.remove = Some(RainConfigRemove::default());
+
+        let merged = RainConfigStruct::default().merge(other);
+
+        assert_eq!(merged.extends, None);
+        assert_eq!(merged.remove, None);
+    }
+
+    #[test]
+    fn normalize_path_collapses_current_and_parent_components() {
+        assert_eq!(normalize_path(Path::new("./src")), PathBuf::from("src"));
+        assert_eq!(normalize_path(Path::new("a/../src")), PathBuf::from("src"));
+        assert_eq!(normalize_path(Path::new("../src")), PathBuf::from("../src"));
+    }
+
+    #[test]
+    fn partition_path_removals_splits_bang_prefixed_entries() {
+        let (keep, removed) = partition_path_removals(paths(&["a", "!b", "c"]));
+        assert_eq!(keep, paths(&["a", "c"]));
+        assert_eq!(removed, paths(&["b"]));
+    }
+
+    #[test]
+    fn partition_string_removals_splits_bang_prefixed_entries() {
+        let (keep, removed) = partition_string_removals(vec![
+            "a".to_string(),
+            "!b".to_string(),
+            "c".to_string(),
+        ]);
+        assert_eq!(keep, vec!["a".to_string(), "c".to_string()]);
+        assert_eq!(removed, vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn interpolate_substitutes_braced_and_bare_vars_with_fallback() {
+        std::env::set_var("RAINCONFIG_TEST_VAR", "value");
+        let out = interpolate(
+            "${RAINCONFIG_TEST_VAR} $RAINCONFIG_TEST_VAR ${MISSING:-fallback}",
+            Path::new("/workspace"),
+        )
+        .unwrap();
+        assert_eq!(out, "value value fallback");
+        std::env::remove_var("RAINCONFIG_TEST_VAR");
+    }
+
+    #[test]
+    fn interpolate_resolves_workspace_root_pseudo_variable() {
+        let out = interpolate("${workspaceRoot}", Path::new("/workspace")).unwrap();
+        assert_eq!(out, "/workspace");
+    }
+
+    #[test]
+    fn interpolate_errors_on_missing_var_without_fallback() {
+        assert!(interpolate("${RAINCONFIG_TEST_MISSING_VAR}", Path::new("/workspace")).is_err());
+    }
+
+    #[test]
+    fn interpolate_json_escapes_substituted_values() {
+        std::env::set_var("RAINCONFIG_TEST_QUOTE_VAR", "a\"b\\c");
+        let out = interpolate("\"${RAINCONFIG_TEST_QUOTE_VAR}\"", Path::new("/workspace")).unwrap();
+        assert_eq!(out, "\"a\\\"b\\\\c\"");
+        std::env::remove_var("RAINCONFIG_TEST_QUOTE_VAR");
+    }
+
+    #[test]
+    fn json_escape_escapes_quotes_and_backslashes() {
+        assert_eq!(json_escape("a\"b\\c"), "a\\\"b\\\\c");
+    }
+
+    #[test]
+    fn compute_cache_key_is_order_independent_over_inputs() {
+        let dotrains_a = vec![
+            (PathBuf::from("b.rain"), "B".to_string()),
+            (PathBuf::from("a.rain"), "A".to_string()),
+        ];
+        let dotrains_b = vec![
+            (PathBuf::from("a.rain"), "A".to_string()),
+            (PathBuf::from("b.rain"), "B".to_string()),
+        ];
+        let key_a = compute_cache_key(&dotrains_a, &[], &[], &[], &["main"], false);
+        let key_b = compute_cache_key(&dotrains_b, &[], &[], &[], &["main"], false);
+        assert_eq!(key_a, key_b);
+    }
+
+    #[test]
+    fn compute_cache_key_changes_with_local_data_only() {
+        let dotrains = vec![(PathBuf::from("a.rain"), "A".to_string())];
+        let key_a = compute_cache_key(&dotrains, &[], &[], &[], &["main"], false);
+        let key_b = compute_cache_key(&dotrains, &[], &[], &[], &["main"], true);
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn local_watch_path_strips_file_scheme_and_skips_remote_sources() {
+        assert_eq!(
+            local_watch_path(&PathBuf::from("file:///tmp/meta.rain")),
+            Some(PathBuf::from("/tmp/meta.rain"))
+        );
+        assert_eq!(
+            local_watch_path(&PathBuf::from("https://example.com/meta")),
+            None
+        );
+        assert_eq!(local_watch_path(&PathBuf::from("ipfs://Qm")), None);
+        assert_eq!(
+            local_watch_path(&PathBuf::from("src/meta.rain")),
+            Some(PathBuf::from("src/meta.rain"))
+        );
+    }
+}
+
+async fn read_bytecode(path: &PathBuf) -> anyhow::Result<ArtifactBytecode> {
+    let content = fetch_bytes(path).await?;
     let json = serde_json::from_slice::<serde_json::Value>(&content)?;
     let deployed_bytecode = &json["deployedBytecode"]["object"];
     let bytecode = &json["bytecode"]["object"];
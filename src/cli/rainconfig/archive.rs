@@ -0,0 +1,134 @@
+use std::{io::Read, path::{Path, PathBuf}};
+use tar::Archive;
+use super::fetch::fetch_bytes;
+
+/// true if `path` names a tar archive (`.tar`/`.tar.gz`/`.tgz`) that should be
+/// walked for `.rain` files instead of being treated as a directory
+pub(crate) fn is_dotrain_archive(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// extracts every `.rain` file contained in the tar(.gz) archive at `path`,
+/// exactly as if its contents had been laid out as a directory and walked by
+/// [`super::read_dotrain_files`]
+pub(crate) async fn read_dotrain_archive(path: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let bytes = fetch_bytes(path).await?;
+    let name = path.to_string_lossy();
+    let reader: Box<dyn Read> = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Box::new(flate2::read::GzDecoder::new(&bytes[..]))
+    } else {
+        Box::new(&bytes[..])
+    };
+
+    let mut files_contents = vec![];
+    let mut archive = Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        if entry_path.extension().is_some_and(|ext| ext == "rain") {
+            let Some(entry_path) = sanitize_entry_path(&entry_path) else {
+                continue;
+            };
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            files_contents.push((path.join(entry_path), content));
+        }
+    }
+    Ok(files_contents)
+}
+
+/// rejects an archive entry path that is absolute or escapes the archive root
+/// via a `..` component - either of which would otherwise let `path.join` (an
+/// absolute entry silently discards `path` entirely) or the joined result
+/// (a `..` entry) point outside of `path` - returning it unchanged otherwise
+fn sanitize_entry_path(entry_path: &Path) -> Option<PathBuf> {
+    if entry_path.is_absolute()
+        || entry_path
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return None;
+    }
+    Some(entry_path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tar::{Builder, Header};
+
+    fn build_tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = Builder::new(Vec::new());
+        for (entry_path, data) in entries {
+            let mut header = Header::new_gnu();
+            // written straight into the name field: `set_path` refuses the
+            // absolute and `..` entry paths the escaping-entry test needs
+            header.as_old_mut().name[..entry_path.len()].copy_from_slice(entry_path.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn write_archive(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "rainconfig-archive-test-{}-{}-{name}",
+            std::process::id(),
+            name.len()
+        ));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn sanitize_entry_path_rejects_absolute_and_parent_dir_entries() {
+        assert_eq!(sanitize_entry_path(Path::new("/etc/passwd.rain")), None);
+        assert_eq!(sanitize_entry_path(Path::new("../escape.rain")), None);
+        assert_eq!(sanitize_entry_path(Path::new("a/../../escape.rain")), None);
+        assert_eq!(
+            sanitize_entry_path(Path::new("nested/file.rain")),
+            Some(PathBuf::from("nested/file.rain"))
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_rain_files_from_a_tar_archive() {
+        let bytes = build_tar(&[
+            ("a.rain", b"a content"),
+            ("b.txt", b"not a rain file"),
+            ("nested/c.rain", b"c content"),
+        ]);
+        let archive_path = write_archive("reads_rain_files.tar", &bytes);
+
+        let files = read_dotrain_archive(&archive_path).await.unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .any(|(p, content)| p.ends_with("a.rain") && content == "a content"));
+        assert!(files
+            .iter()
+            .any(|(p, content)| p.ends_with("nested/c.rain") && content == "c content"));
+
+        std::fs::remove_file(&archive_path).ok();
+    }
+
+    #[tokio::test]
+    async fn skips_entries_escaping_the_archive_root() {
+        let bytes = build_tar(&[
+            ("/etc/passwd.rain", b"malicious"),
+            ("../../escape.rain", b"malicious"),
+            ("safe.rain", b"safe content"),
+        ]);
+        let archive_path = write_archive("skips_unsafe_entries.tar", &bytes);
+
+        let files = read_dotrain_archive(&archive_path).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].0.ends_with("safe.rain"));
+
+        std::fs::remove_file(&archive_path).ok();
+    }
+}
@@ -0,0 +1,108 @@
+use std::{io::Read, path::Path};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// transparently decompresses `data` if it looks gzip or zstd encoded (detected
+/// by magic bytes), returning `data` unchanged otherwise. `source` additionally
+/// gates brotli decompression, which is attempted only when `source` carries a
+/// `.br` suffix: brotli has no magic bytes of its own, and its decoder isn't
+/// guaranteed to reject arbitrary non-brotli input, so attempting it against
+/// every plain/uncompressed meta would risk silently replacing a valid meta
+/// with garbage decoded bytes before its hash is taken
+pub(crate) fn decompress(data: Vec<u8>, source: &Path) -> Vec<u8> {
+    if data.starts_with(&GZIP_MAGIC) {
+        if let Some(decompressed) = decompress_gzip(&data) {
+            return decompressed;
+        }
+    } else if data.starts_with(&ZSTD_MAGIC) {
+        if let Ok(decompressed) = zstd::stream::decode_all(&data[..]) {
+            return decompressed;
+        }
+    } else if source.extension().is_some_and(|ext| ext == "br") {
+        if let Some(decompressed) = decompress_brotli(&data) {
+            return decompressed;
+        }
+    }
+    data
+}
+
+fn decompress_gzip(data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(data)
+        .read_to_end(&mut decoded)
+        .ok()?;
+    Some(decoded)
+}
+
+fn decompress_brotli(data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    brotli::BrotliDecompress(&mut &data[..], &mut decoded).ok()?;
+    (!decoded.is_empty()).then_some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zstd(data: &[u8]) -> Vec<u8> {
+        zstd::stream::encode_all(data, 0).unwrap()
+    }
+
+    #[test]
+    fn decompresses_gzip_by_magic_bytes() {
+        let plain = b"hello world".to_vec();
+        let compressed = gzip(&plain);
+        assert_eq!(decompress(compressed, Path::new("meta")), plain);
+    }
+
+    #[test]
+    fn decompresses_zstd_by_magic_bytes() {
+        let plain = b"hello world".to_vec();
+        let compressed = zstd(&plain);
+        assert_eq!(decompress(compressed, Path::new("meta")), plain);
+    }
+
+    #[test]
+    fn leaves_uncompressed_data_unchanged() {
+        let plain = b"hello world".to_vec();
+        assert_eq!(decompress(plain.clone(), Path::new("meta")), plain);
+    }
+
+    #[test]
+    fn ignores_brotli_data_without_br_suffix() {
+        let mut compressed = Vec::new();
+        brotli::enc::BrotliCompress(
+            &mut &b"hello world"[..],
+            &mut compressed,
+            &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            decompress(compressed.clone(), Path::new("meta")),
+            compressed
+        );
+    }
+
+    #[test]
+    fn attempts_brotli_only_with_br_suffix() {
+        let mut compressed = Vec::new();
+        brotli::enc::BrotliCompress(
+            &mut &b"hello world"[..],
+            &mut compressed,
+            &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            decompress(compressed, Path::new("meta.br")),
+            b"hello world".to_vec()
+        );
+    }
+}
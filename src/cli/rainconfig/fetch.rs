@@ -0,0 +1,31 @@
+use std::{fs, path::Path};
+
+/// default public IPFS gateway used to resolve `ipfs://` sources
+const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// fetches the raw bytes of a meta/deployer artifact source, dispatching on its
+/// URI scheme: `file://` and bare paths are read from local disk, `http(s)://`
+/// is fetched over the network and `ipfs://` is resolved through a public
+/// gateway. Callers already treat a read failure as skippable under `force`,
+/// so an unreachable remote source naturally falls out of the build rather
+/// than aborting it. Async so it can be awaited from inside the composer's
+/// existing tokio runtime rather than blocking it.
+pub(crate) async fn fetch_bytes(source: &Path) -> anyhow::Result<Vec<u8>> {
+    let addr = source.to_string_lossy();
+    if let Some(local_path) = addr.strip_prefix("file://") {
+        return Ok(fs::read(local_path)?);
+    }
+    if addr.starts_with("http://") || addr.starts_with("https://") {
+        return fetch_http(&addr).await;
+    }
+    if let Some(cid) = addr.strip_prefix("ipfs://") {
+        return fetch_http(&format!("{IPFS_GATEWAY}{cid}")).await;
+    }
+    Ok(fs::read(source)?)
+}
+
+/// performs an HTTP GET and returns the response body as bytes
+async fn fetch_http(url: &str) -> anyhow::Result<Vec<u8>> {
+    let response = reqwest::get(url).await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}